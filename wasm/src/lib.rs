@@ -1,6 +1,9 @@
 mod string_physics;
 mod matrix_physics;
 mod calabi_yau;
+mod linalg;
+mod integrators;
+mod universe;
 
 use wasm_bindgen::prelude::*;
 
@@ -13,3 +16,4 @@ pub fn init() {
 pub use string_physics::StringSimulation;
 pub use matrix_physics::MatrixSimulation;
 pub use calabi_yau::CalabiYauMesh;
+pub use universe::PhysicsWorld;
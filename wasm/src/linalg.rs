@@ -0,0 +1,135 @@
+//! Small dense linear-algebra helpers shared by the physics modules.
+//!
+//! The matrices involved here are tiny (3×3 inertia tensors, or D0-brane
+//! counts in the tens), so the classical Jacobi eigenvalue method
+//! is both simple and fast enough — no need to reach for a full LAPACK
+//! binding inside the wasm build.
+
+const JACOBI_MAX_SWEEPS: usize = 100;
+const JACOBI_TOL: f64 = 1e-10;
+
+/// Diagonalizes a real symmetric `n`×`n` matrix (row-major, length `n*n`)
+/// via the classical Jacobi rotation method.
+///
+/// Returns `(eigenvalues, eigenvectors)` where `eigenvalues` is ascending
+/// and `eigenvectors` is row-major with eigenvector `b` occupying column
+/// `b`, i.e. `eigenvectors[a * n + b]` is the a-th component of the
+/// eigenvector for `eigenvalues[b]`.
+pub(crate) fn jacobi_eigen(matrix: &[f64], n: usize) -> (Vec<f64>, Vec<f64>) {
+    let mut a = matrix.to_vec();
+    let mut v = vec![0.0f64; n * n];
+    for i in 0..n {
+        v[i * n + i] = 1.0;
+    }
+
+    // One sweep is n(n-1)/2 rotations; each iteration below is one rotation.
+    let max_rotations = JACOBI_MAX_SWEEPS * n * n.saturating_sub(1) / 2;
+    for _ in 0..max_rotations {
+        // Find the largest-magnitude off-diagonal element and the total
+        // off-diagonal energy (our convergence criterion).
+        let mut off_sq = 0.0f64;
+        let mut max_val = 0.0f64;
+        let (mut p, mut q) = (0usize, 1usize);
+        for i in 0..n {
+            for j in (i + 1)..n {
+                let aij = a[i * n + j];
+                off_sq += 2.0 * aij * aij;
+                if aij.abs() > max_val {
+                    max_val = aij.abs();
+                    p = i;
+                    q = j;
+                }
+            }
+        }
+        if off_sq < JACOBI_TOL || max_val < 1e-300 {
+            break;
+        }
+
+        let app = a[p * n + p];
+        let aqq = a[q * n + q];
+        let apq = a[p * n + q];
+
+        let theta = if (app - aqq).abs() < 1e-14 {
+            // Degenerate diagonal: cot(2theta) is undefined, the rotation
+            // that annihilates a[p][q] is the 45-degree one.
+            std::f64::consts::FRAC_PI_4
+        } else {
+            let cot_2theta = (aqq - app) / (2.0 * apq);
+            0.5 * (1.0 / cot_2theta).atan()
+        };
+        let (s, c) = theta.sin_cos();
+
+        // Two-sided rotation of rows/columns p, q of A.
+        for k in 0..n {
+            if k != p && k != q {
+                let akp = a[k * n + p];
+                let akq = a[k * n + q];
+                let new_akp = c * akp - s * akq;
+                let new_akq = s * akp + c * akq;
+                a[k * n + p] = new_akp;
+                a[p * n + k] = new_akp;
+                a[k * n + q] = new_akq;
+                a[q * n + k] = new_akq;
+            }
+        }
+        a[p * n + p] = c * c * app - 2.0 * s * c * apq + s * s * aqq;
+        a[q * n + q] = s * s * app + 2.0 * s * c * apq + c * c * aqq;
+        a[p * n + q] = 0.0;
+        a[q * n + p] = 0.0;
+
+        // Accumulate the rotation into the eigenvector basis.
+        for k in 0..n {
+            let vkp = v[k * n + p];
+            let vkq = v[k * n + q];
+            v[k * n + p] = c * vkp - s * vkq;
+            v[k * n + q] = s * vkp + c * vkq;
+        }
+    }
+
+    let diag: Vec<f64> = (0..n).map(|i| a[i * n + i]).collect();
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|&i, &j| diag[i].partial_cmp(&diag[j]).unwrap());
+
+    let sorted_values: Vec<f64> = order.iter().map(|&i| diag[i]).collect();
+    let mut sorted_vectors = vec![0.0f64; n * n];
+    for (b, &src_col) in order.iter().enumerate() {
+        for a_row in 0..n {
+            sorted_vectors[a_row * n + b] = v[a_row * n + src_col];
+        }
+    }
+
+    (sorted_values, sorted_vectors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn jacobi_eigen_diagonal_matrix_returns_diagonal_sorted() {
+        let matrix = vec![3.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 2.0];
+        let (values, _vectors) = jacobi_eigen(&matrix, 3);
+        assert!((values[0] - 1.0).abs() < 1e-9);
+        assert!((values[1] - 2.0).abs() < 1e-9);
+        assert!((values[2] - 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn jacobi_eigen_reconstructs_original_matrix() {
+        // A * v_b == lambda_b * v_b for every eigenpair, i.e. A == V * diag(values) * V^T.
+        let n = 3;
+        let matrix = vec![2.0, 1.0, 0.0, 1.0, 2.0, 1.0, 0.0, 1.0, 2.0];
+        let (values, vectors) = jacobi_eigen(&matrix, n);
+
+        for b in 0..n {
+            for row in 0..n {
+                let mut lhs = 0.0;
+                for col in 0..n {
+                    lhs += matrix[row * n + col] * vectors[col * n + b];
+                }
+                let rhs = values[b] * vectors[row * n + b];
+                assert!((lhs - rhs).abs() < 1e-8, "row {row}, eigenpair {b}: {lhs} != {rhs}");
+            }
+        }
+    }
+}
@@ -1,5 +1,8 @@
 use wasm_bindgen::prelude::*;
 
+use crate::integrators::{self, Integrator};
+use crate::linalg::jacobi_eigen;
+
 /// BFSS Matrix Model (D0-Branes)
 /// Bosonic Hamiltonian: H = Tr(0.5*P^2 - 0.25*[Xi,Xj]^2 + 0.5*m^2*Xi^2)
 #[wasm_bindgen]
@@ -12,6 +15,7 @@ pub struct MatrixSimulation {
     mass: f64,
     coupling: f64,
     damping: f64,
+    integrator: Box<dyn Integrator>,
 }
 
 // Maximum force magnitude per element — prevents first-frame blowup
@@ -21,6 +25,12 @@ const MOM_CLAMP: f64 = 3.0;
 // Maximum position change per substep — prevents runaway
 const POS_STEP_CLAMP: f64 = 0.15;
 
+// Annealing start/end temperatures for the geometric cooling schedule
+const ANNEAL_T0: f64 = 2.0;
+const ANNEAL_T1: f64 = 0.02;
+// Magnitude of the proposed per-entry perturbation during annealing
+const ANNEAL_STEP: f64 = 0.1;
+
 #[wasm_bindgen]
 impl MatrixSimulation {
     #[wasm_bindgen(constructor)]
@@ -43,30 +53,71 @@ impl MatrixSimulation {
         }
 
         // Damping high enough to absorb startup transient without overdamping dynamics
-        MatrixSimulation { n, x, p, mass, coupling, damping: 0.08 }
+        MatrixSimulation {
+            n,
+            x,
+            p,
+            mass,
+            coupling,
+            damping: 0.08,
+            integrator: integrators::from_mode(0),
+        }
+    }
+
+    /// Selects the time-stepping scheme used by `step`: 0 = velocity
+    /// Verlet (the default), 1 = semi-implicit Euler, 2 = RK4. RK4
+    /// tolerates a larger `dt` before the energy drift Verlet shows.
+    pub fn set_integrator(&mut self, mode: u32) {
+        self.integrator = integrators::from_mode(mode);
     }
 
     pub fn step(&mut self, dt: f64) {
         let n = self.n;
-        let dt2 = dt * dt;
-
-        let forces = self.compute_forces();
+        let block = n * n;
+        let mass = self.mass;
+        let coupling = self.coupling;
 
-        // Velocity Verlet: update positions, clamping per-step displacement
+        // State = [X1, X2, X3, P1, P2, P3], each a flat block of n*n.
+        let mut state = vec![0.0f64; 6 * block];
         for i in 0..3 {
-            for idx in 0..(n * n) {
-                let dx = self.p[i][idx] * dt + 0.5 * forces[i][idx] * dt2;
-                self.x[i][idx] += dx.clamp(-POS_STEP_CLAMP, POS_STEP_CLAMP);
-            }
+            state[i * block..(i + 1) * block].copy_from_slice(&self.x[i]);
+            state[(3 + i) * block..(4 + i) * block].copy_from_slice(&self.p[i]);
         }
+        let old_x = state[..3 * block].to_vec();
+
+        let derivative = move |s: &[f64]| -> Vec<f64> {
+            let xs: Vec<Vec<f64>> = (0..3).map(|i| s[i * block..(i + 1) * block].to_vec()).collect();
+            let ps: Vec<&[f64]> = (0..3).map(|i| &s[(3 + i) * block..(4 + i) * block]).collect();
+            let forces = compute_forces(&xs, n, mass, coupling);
+
+            let mut out = vec![0.0f64; 6 * block];
+            for i in 0..3 {
+                out[i * block..(i + 1) * block].copy_from_slice(ps[i]);
+                out[(3 + i) * block..(4 + i) * block].copy_from_slice(&forces[i]);
+            }
+            out
+        };
 
-        let forces_new = self.compute_forces();
+        self.integrator.step(&mut state, dt, &derivative);
+
+        // Clamp per-step displacement to prevent runaway. Note this is
+        // applied post-hoc to the integrator's final position, unlike the
+        // old hand-rolled Verlet, which clamped the position *before*
+        // evaluating the second (end-of-step) force and folding it into
+        // the velocity update. Generic `Integrator` impls compute their
+        // internal stages on the raw, unclamped position, so the velocity
+        // update can now differ slightly from the pre-refactor behavior
+        // whenever a step's excursion would have hit POS_STEP_CLAMP.
+        for idx in 0..(3 * block) {
+            let dx = (state[idx] - old_x[idx]).clamp(-POS_STEP_CLAMP, POS_STEP_CLAMP);
+            state[idx] = old_x[idx] + dx;
+        }
 
         let damp = 1.0 - self.damping * dt;
         for i in 0..3 {
-            for idx in 0..(n * n) {
-                let dp = 0.5 * (forces[i][idx] + forces_new[i][idx]) * dt;
-                self.p[i][idx] = (damp * (self.p[i][idx] + dp)).clamp(-MOM_CLAMP, MOM_CLAMP);
+            for idx in 0..block {
+                self.x[i][idx] = state[i * block + idx];
+                self.p[i][idx] = (damp * state[(3 + i) * block + idx]).clamp(-MOM_CLAMP, MOM_CLAMP);
             }
         }
 
@@ -82,30 +133,58 @@ impl MatrixSimulation {
         }
     }
 
-    fn compute_forces(&self) -> Vec<Vec<f64>> {
-        let n = self.n;
-        let mut forces = vec![vec![0.0f64; n * n]; 3];
+    /// Runs `iterations` steps of Metropolis-style simulated annealing to
+    /// relax the matrices toward a minimum-energy (ground-state) bound
+    /// state, distinct from the live dynamics in `step`.
+    pub fn relax(&mut self, iterations: u32) {
+        for k in 0..iterations {
+            let progress = if iterations <= 1 {
+                1.0
+            } else {
+                k as f64 / (iterations - 1) as f64
+            };
+            self.anneal_step(progress);
+        }
+    }
 
+    /// One Metropolis annealing move at the given `progress` (0 at the
+    /// start of a run, 1 at the end). Temperature follows the geometric
+    /// cooling schedule T = T0^(1-progress) * T1^progress, so moves that
+    /// raise the energy are likely accepted early (escaping local minima)
+    /// and unlikely late (freezing into the vacuum). Momenta are zeroed
+    /// throughout since annealing only explores configuration space.
+    pub fn anneal_step(&mut self, progress: f64) {
+        let n = self.n;
+        if n == 0 {
+            return;
+        }
         for i in 0..3 {
-            for j in 0..3 {
-                if i == j { continue; }
-                // f_i += coupling^2 * [X_j, [X_j, X_i]]
-                let comm_ji = commutator(&self.x[j], &self.x[i], n);
-                let double_comm = commutator(&self.x[j], &comm_ji, n);
-                for idx in 0..(n * n) {
-                    forces[i][idx] += self.coupling * self.coupling * double_comm[idx];
-                }
-            }
-            // Mass / confinement term: f_i -= m^2 * X_i
             for idx in 0..(n * n) {
-                forces[i][idx] -= self.mass * self.mass * self.x[i][idx];
-            }
-            // Clamp to prevent first-frame numerical explosion
-            for idx in 0..(n * n) {
-                forces[i][idx] = forces[i][idx].clamp(-FORCE_CLAMP, FORCE_CLAMP);
+                self.p[i][idx] = 0.0;
             }
         }
-        forces
+
+        let temperature = ANNEAL_T0.powf(1.0 - progress) * ANNEAL_T1.powf(progress);
+
+        let mat_idx = ((js_random() * 3.0) as usize).min(2);
+        let a = ((js_random() * n as f64) as usize).min(n - 1);
+        let b = ((js_random() * n as f64) as usize).min(n - 1);
+        let delta = (js_random() - 0.5) * ANNEAL_STEP;
+
+        let energy_before = self.get_energy();
+        let old_ab = self.x[mat_idx][a * n + b];
+        let old_ba = self.x[mat_idx][b * n + a];
+
+        let new_ab = old_ab + delta;
+        self.x[mat_idx][a * n + b] = new_ab;
+        self.x[mat_idx][b * n + a] = new_ab; // keep Hermitian
+
+        let delta_e = self.get_energy() - energy_before;
+        let accept = delta_e < 0.0 || js_random() < (-delta_e / temperature).exp();
+        if !accept {
+            self.x[mat_idx][a * n + b] = old_ab;
+            self.x[mat_idx][b * n + a] = old_ba;
+        }
     }
 
     /// Poke: add a symmetrized random momentum kick to each matrix
@@ -130,7 +209,10 @@ impl MatrixSimulation {
         self.mass = mass;
     }
 
-    /// Returns eigenvalue proxies (diagonal elements) as flat [n * 3] array
+    /// Returns eigenvalue proxies (diagonal elements) as flat [n * 3] array.
+    /// Superseded by [`MatrixSimulation::get_spectrum`] once the matrices
+    /// develop off-diagonal structure, but kept for callers that only want
+    /// the cheap diagonal read.
     pub fn get_eigenvalues(&self) -> Vec<f64> {
         let n = self.n;
         let mut out = Vec::with_capacity(n * 3);
@@ -142,6 +224,24 @@ impl MatrixSimulation {
         out
     }
 
+    /// Returns the true eigenvalue spectrum of `X_{matrix_index}` (the
+    /// D0-brane positions) via Jacobi diagonalization, rather than the
+    /// diagonal-proxy values `get_eigenvalues` returns.
+    ///
+    /// Layout: `[lambda_0..lambda_{n-1}, V]` where `V` is the row-major
+    /// n×n eigenvector basis (column `b` of `V` is the eigenvector for
+    /// `lambda_b`) — the D0-brane eigenframe.
+    pub fn get_spectrum(&self, matrix_index: u32) -> Vec<f64> {
+        let i = (matrix_index as usize) % 3;
+        let n = self.n;
+        let (eigenvalues, eigenvectors) = jacobi_eigen(&self.x[i], n);
+
+        let mut out = Vec::with_capacity(n + n * n);
+        out.extend(eigenvalues);
+        out.extend(eigenvectors);
+        out
+    }
+
     /// Returns connection strengths: flat [a, b, strength, ...] triples
     pub fn get_connections(&self) -> Vec<f64> {
         let n = self.n;
@@ -184,6 +284,36 @@ impl MatrixSimulation {
     }
 }
 
+/// Computes the BFSS force on each of the three matrices at the given
+/// configuration `x`: commutator terms coupling the matrices plus the
+/// mass/confinement term, clamped to prevent first-frame blowup. Free
+/// function (rather than a method) so the integrator's derivative
+/// closure can evaluate it at intermediate states, not just `self.x`.
+fn compute_forces(x: &[Vec<f64>], n: usize, mass: f64, coupling: f64) -> Vec<Vec<f64>> {
+    let mut forces = vec![vec![0.0f64; n * n]; 3];
+
+    for i in 0..3 {
+        for j in 0..3 {
+            if i == j { continue; }
+            // f_i += coupling^2 * [X_j, [X_j, X_i]]
+            let comm_ji = commutator(&x[j], &x[i], n);
+            let double_comm = commutator(&x[j], &comm_ji, n);
+            for idx in 0..(n * n) {
+                forces[i][idx] += coupling * coupling * double_comm[idx];
+            }
+        }
+        // Mass / confinement term: f_i -= m^2 * X_i
+        for idx in 0..(n * n) {
+            forces[i][idx] -= mass * mass * x[i][idx];
+        }
+        // Clamp to prevent first-frame numerical explosion
+        for idx in 0..(n * n) {
+            forces[i][idx] = forces[i][idx].clamp(-FORCE_CLAMP, FORCE_CLAMP);
+        }
+    }
+    forces
+}
+
 /// [A, B] = AB - BA for n×n real matrices (flat row-major storage)
 fn commutator(a: &[f64], b: &[f64], n: usize) -> Vec<f64> {
     let mut result = vec![0.0f64; n * n];
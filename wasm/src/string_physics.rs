@@ -1,11 +1,28 @@
 use wasm_bindgen::prelude::*;
 use serde::{Deserialize, Serialize};
 
+use crate::integrators::{self, Integrator};
+use crate::linalg::jacobi_eigen;
+
 const MIN_LOOP_POINTS: usize = 20;
 const INTERSECTION_THRESHOLD: f64 = 0.8;
 const TARGET_POINT_DENSITY: f64 = 0.5;
 const MAX_LOOPS: usize = 8;
 
+// Conjugate-gradient settings for the implicit backward-Euler solve
+const CG_MAX_ITER: usize = 20;
+const CG_TOL: f64 = 1e-6;
+
+/// Either a swappable explicit scheme from the shared `integrators`
+/// module (Verlet, semi-implicit Euler, RK4) or the unconditionally
+/// stable implicit backward-Euler/CG solve, which needs the ring
+/// Laplacian structure directly and so isn't expressible as a generic
+/// `Integrator`.
+enum IntegratorMode {
+    Explicit(Box<dyn Integrator>),
+    ImplicitCg,
+}
+
 #[derive(Clone, Serialize, Deserialize)]
 pub struct StringLoop {
     pub positions: Vec<[f64; 3]>,
@@ -89,6 +106,7 @@ pub struct StringSimulation {
     loops: Vec<StringLoop>,
     coupling: f64,
     next_color_id: u32,
+    integrator: IntegratorMode,
 }
 
 #[wasm_bindgen]
@@ -102,49 +120,58 @@ impl StringSimulation {
             loops,
             coupling,
             next_color_id: 2,
+            integrator: IntegratorMode::Explicit(integrators::from_mode(0)),
         }
     }
 
+    /// Selects the time-stepping scheme used by `step`: 0 = velocity
+    /// Verlet (the default), 1 = semi-implicit Euler, 2 = RK4 — all
+    /// cheap but conditionally stable — or 3 = implicit backward-Euler
+    /// via conjugate gradient, unconditionally stable with no velocity
+    /// clamp needed even at high `coupling`/`dt`. Any other code falls
+    /// back to velocity Verlet.
+    pub fn set_integrator(&mut self, mode: u32) {
+        self.integrator = if mode == 3 {
+            IntegratorMode::ImplicitCg
+        } else {
+            IntegratorMode::Explicit(integrators::from_mode(mode))
+        };
+    }
+
     pub fn step(&mut self, dt: f64) {
-        let dt2 = dt * dt;
-        for lp in &mut self.loops {
-            let n = lp.len();
-            let mut forces: Vec<[f64; 3]> = vec![[0.0; 3]; n];
+        match &self.integrator {
+            IntegratorMode::Explicit(scheme) => {
+                let coupling = self.coupling;
+                for lp in &mut self.loops {
+                    let n = lp.len();
+                    for d in 0..3 {
+                        let mut state: Vec<f64> = Vec::with_capacity(2 * n);
+                        state.extend(lp.positions.iter().map(|p| p[d]));
+                        state.extend(lp.velocities.iter().map(|v| v[d]));
 
-            // Compute Laplacian-based string forces (wave equation in conformal gauge)
-            for i in 0..n {
-                let prev = if i == 0 { n - 1 } else { i - 1 };
-                let next = (i + 1) % n;
-                for d in 0..3 {
-                    let laplacian = lp.positions[prev][d] - 2.0 * lp.positions[i][d] + lp.positions[next][d];
-                    forces[i][d] = lp.coupling_force(laplacian, self.coupling);
-                }
-            }
+                        let derivative = |s: &[f64]| -> Vec<f64> {
+                            let x = &s[..n];
+                            let v = &s[n..];
+                            let mut out = vec![0.0f64; 2 * n];
+                            out[..n].copy_from_slice(v);
+                            for i in 0..n {
+                                let prev = if i == 0 { n - 1 } else { i - 1 };
+                                let next = (i + 1) % n;
+                                let laplacian = x[prev] - 2.0 * x[i] + x[next];
+                                out[n + i] = coupling * laplacian;
+                            }
+                            out
+                        };
+                        scheme.step(&mut state, dt, &derivative);
 
-            // Velocity Verlet integration
-            for i in 0..n {
-                for d in 0..3 {
-                    lp.positions[i][d] += lp.velocities[i][d] * dt + 0.5 * forces[i][d] * dt2;
-                }
-            }
-            // Recompute forces at new positions
-            let mut forces_new: Vec<[f64; 3]> = vec![[0.0; 3]; n];
-            for i in 0..n {
-                let prev = if i == 0 { n - 1 } else { i - 1 };
-                let next = (i + 1) % n;
-                for d in 0..3 {
-                    let laplacian = lp.positions[prev][d] - 2.0 * lp.positions[i][d] + lp.positions[next][d];
-                    forces_new[i][d] = lp.coupling_force(laplacian, self.coupling);
-                }
-            }
-            // Update velocities
-            for i in 0..n {
-                for d in 0..3 {
-                    lp.velocities[i][d] += 0.5 * (forces[i][d] + forces_new[i][d]) * dt;
-                    // Clamp velocity for stability
-                    lp.velocities[i][d] = lp.velocities[i][d].clamp(-5.0, 5.0);
+                        for i in 0..n {
+                            lp.positions[i][d] = state[i];
+                            lp.velocities[i][d] = state[n + i].clamp(-5.0, 5.0);
+                        }
+                    }
                 }
             }
+            IntegratorMode::ImplicitCg => self.step_implicit(dt),
         }
 
         // Check for self-intersections and split
@@ -156,6 +183,33 @@ impl StringSimulation {
         self.resample_loops();
     }
 
+    /// Implicit (backward-Euler) integration via conjugate gradient —
+    /// unconditionally stable, so no velocity clamp is needed even at
+    /// high `coupling`/`dt`. Solves, per coordinate and per loop,
+    /// `(I - dt^2 * c * L) v_{n+1} = v_n + dt * c * L * x_n` where `L`
+    /// is the cyclic ring Laplacian, using the cheap matrix-free stencil
+    /// as the CG matvec (the matrix is never materialized).
+    fn step_implicit(&mut self, dt: f64) {
+        let coupling = self.coupling;
+        for lp in &mut self.loops {
+            let n = lp.len();
+            for d in 0..3 {
+                let x: Vec<f64> = lp.positions.iter().map(|p| p[d]).collect();
+                let v: Vec<f64> = lp.velocities.iter().map(|p| p[d]).collect();
+
+                let lx = ring_laplacian(&x);
+                let rhs: Vec<f64> = (0..n).map(|i| v[i] + dt * coupling * lx[i]).collect();
+
+                let v_new = conjugate_gradient(&rhs, &v, dt * dt * coupling);
+
+                for (i, &vn) in v_new.iter().enumerate() {
+                    lp.velocities[i][d] = vn;
+                    lp.positions[i][d] += dt * vn;
+                }
+            }
+        }
+    }
+
     fn check_intersections(&mut self) {
         let mut new_loops: Vec<StringLoop> = Vec::new();
         let mut to_remove: Vec<usize> = Vec::new();
@@ -249,6 +303,51 @@ impl StringSimulation {
         out
     }
 
+    /// Returns the moment-of-inertia tensor of loop `loop_index`,
+    /// diagonalized via a 3×3 Jacobi sweep: `I = sum(|r|^2 * Identity -
+    /// r⊗r^T)` over the centroid-relative point cloud. Layout: `[m0, m1,
+    /// m2, V]` — the three principal moments (ascending) followed by the
+    /// row-major 3×3 principal-axis basis (column `b` of `V` is the axis
+    /// for `m_b`). Ratios between the moments classify the loop's shape
+    /// (round vs. elongated vs. flattened) and flag near-pinching before
+    /// `check_intersections` actually splits it. Returns an empty vec for
+    /// an out-of-range `loop_index`.
+    pub fn get_inertia(&self, loop_index: u32) -> Vec<f64> {
+        let idx = loop_index as usize;
+        let Some(lp) = self.loops.get(idx) else {
+            return Vec::new();
+        };
+        let n = lp.len();
+
+        let mut centroid = [0.0f64; 3];
+        for p in &lp.positions {
+            for (c, &pd) in centroid.iter_mut().zip(p.iter()) {
+                *c += pd;
+            }
+        }
+        for c in centroid.iter_mut() {
+            *c /= n as f64;
+        }
+
+        let mut inertia = [0.0f64; 9];
+        for p in &lp.positions {
+            let r = [p[0] - centroid[0], p[1] - centroid[1], p[2] - centroid[2]];
+            let r_sq = r[0] * r[0] + r[1] * r[1] + r[2] * r[2];
+            for a in 0..3 {
+                for b in 0..3 {
+                    let identity = if a == b { 1.0 } else { 0.0 };
+                    inertia[a * 3 + b] += r_sq * identity - r[a] * r[b];
+                }
+            }
+        }
+
+        let (moments, axes) = jacobi_eigen(&inertia, 3);
+        let mut out = Vec::with_capacity(3 + 9);
+        out.extend(moments);
+        out.extend(axes);
+        out
+    }
+
     pub fn get_total_energy(&self) -> f64 {
         self.loops.iter().map(|l| l.energy()).sum()
     }
@@ -258,10 +357,63 @@ impl StringSimulation {
     }
 }
 
-impl StringLoop {
-    fn coupling_force(&self, laplacian: f64, coupling: f64) -> f64 {
-        coupling * laplacian
+/// Applies the cyclic tridiagonal ring Laplacian (stencil -2 on the
+/// diagonal, +1 to each neighbor with wraparound) to `v` without ever
+/// materializing the matrix.
+fn ring_laplacian(v: &[f64]) -> Vec<f64> {
+    let n = v.len();
+    (0..n)
+        .map(|i| {
+            let prev = if i == 0 { n - 1 } else { i - 1 };
+            let next = (i + 1) % n;
+            v[prev] - 2.0 * v[i] + v[next]
+        })
+        .collect()
+}
+
+/// Solves `(I - dt2c * L) x = rhs` for the symmetric-positive-definite
+/// backward-Euler system via conjugate gradient, using `x0` (the
+/// previous velocity) as the initial guess. `L` is applied matrix-free
+/// via `ring_laplacian`.
+fn conjugate_gradient(rhs: &[f64], x0: &[f64], dt2c: f64) -> Vec<f64> {
+    let n = rhs.len();
+    let apply = |v: &[f64]| -> Vec<f64> {
+        let lv = ring_laplacian(v);
+        (0..n).map(|i| v[i] - dt2c * lv[i]).collect()
+    };
+
+    let mut x = x0.to_vec();
+    let ax = apply(&x);
+    let mut r: Vec<f64> = (0..n).map(|i| rhs[i] - ax[i]).collect();
+    let mut p = r.clone();
+    let mut rs_old: f64 = r.iter().map(|v| v * v).sum();
+
+    for _ in 0..CG_MAX_ITER {
+        if rs_old.sqrt() < CG_TOL {
+            break;
+        }
+        let ap = apply(&p);
+        let pap: f64 = p.iter().zip(&ap).map(|(a, b)| a * b).sum();
+        if pap.abs() < 1e-300 {
+            break;
+        }
+        let alpha = rs_old / pap;
+        for i in 0..n {
+            x[i] += alpha * p[i];
+            r[i] -= alpha * ap[i];
+        }
+        let rs_new: f64 = r.iter().map(|v| v * v).sum();
+        if rs_new.sqrt() < CG_TOL {
+            break;
+        }
+        let beta = rs_new / rs_old;
+        for i in 0..n {
+            p[i] = r[i] + beta * p[i];
+        }
+        rs_old = rs_new;
     }
+
+    x
 }
 
 fn dist3d(a: [f64; 3], b: [f64; 3]) -> f64 {
@@ -290,3 +442,33 @@ fn split_loop(lp: &StringLoop, i: usize, j: usize, c1: u32, c2: u32) -> (StringL
 fn js_random() -> f64 {
     js_sys::Math::random()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn conjugate_gradient_solves_ring_system() {
+        // (I - dt2c * L) x = rhs, solved then checked by re-applying the
+        // same matrix-free operator and comparing against rhs.
+        let rhs = vec![1.0, 0.5, -0.5, -1.0, 0.0, 0.25];
+        let dt2c = 0.1;
+        let x = conjugate_gradient(&rhs, &vec![0.0; rhs.len()], dt2c);
+
+        let lx = ring_laplacian(&x);
+        let n = rhs.len();
+        for i in 0..n {
+            let applied = x[i] - dt2c * lx[i];
+            assert!((applied - rhs[i]).abs() < 1e-6, "index {i}: {applied} != {rhs_i}", rhs_i = rhs[i]);
+        }
+    }
+
+    #[test]
+    fn conjugate_gradient_zero_rhs_is_zero() {
+        let rhs = vec![0.0; 4];
+        let x = conjugate_gradient(&rhs, &vec![0.0; 4], 0.2);
+        for v in x {
+            assert!(v.abs() < 1e-9);
+        }
+    }
+}
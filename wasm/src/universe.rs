@@ -0,0 +1,149 @@
+use wasm_bindgen::prelude::*;
+
+use crate::calabi_yau::CalabiYauMesh;
+use crate::matrix_physics::MatrixSimulation;
+use crate::string_physics::StringSimulation;
+
+/// Fixed micro-step size used by `advance`'s accumulator. Small enough
+/// that a variable host frame time still maps to a stable integer number
+/// of substeps per frame.
+const SUBSTEP_DT: f64 = 1.0 / 240.0;
+/// Caps substeps per `advance` call so a stalled host (huge `dt`) can't
+/// wedge the simulation in a multi-second catch-up loop. Paired with the
+/// accumulator clamp in `advance`, which drops stale backlog instead of
+/// queueing it for future frames to silently fast-forward through.
+const MAX_SUBSTEPS_PER_FRAME: u32 = 16;
+/// Largest backlog the accumulator is allowed to carry between `advance`
+/// calls — exactly the amount one call can drain.
+const MAX_ACCUMULATOR: f64 = MAX_SUBSTEPS_PER_FRAME as f64 * SUBSTEP_DT;
+
+/// Unifies the three independent simulations behind one object:
+/// `StringSimulation` and `MatrixSimulation` are both stepped on one
+/// synchronized clock by a single `advance(dt)` call, and the Calabi-Yau
+/// mesh parameters are stored here, with the mesh itself built on demand
+/// by `generate_cy_mesh` rather than by `advance`.
+#[wasm_bindgen]
+pub struct PhysicsWorld {
+    strings: StringSimulation,
+    matrices: MatrixSimulation,
+    cy_slice_z: f64,
+    cy_psi: f64,
+    accumulator: f64,
+}
+
+#[wasm_bindgen]
+impl PhysicsWorld {
+    #[wasm_bindgen(constructor)]
+    pub fn new(string_coupling: f64, matrix_n: u32, matrix_coupling: f64, matrix_mass: f64) -> Self {
+        PhysicsWorld {
+            strings: StringSimulation::new(string_coupling),
+            matrices: MatrixSimulation::new(matrix_n, matrix_coupling, matrix_mass),
+            cy_slice_z: 0.0,
+            cy_psi: 1.0,
+            accumulator: 0.0,
+        }
+    }
+
+    pub fn set_cy_params(&mut self, slice_z: f64, psi: f64) {
+        self.cy_slice_z = slice_z;
+        self.cy_psi = psi;
+    }
+
+    /// Generates the Calabi-Yau mesh for the world's stored slice/psi
+    /// parameters at the given `resolution` (see `CalabiYauMesh::generate`).
+    pub fn generate_cy_mesh(&self, resolution: u32) -> js_sys::Float32Array {
+        CalabiYauMesh::generate(resolution, self.cy_slice_z, self.cy_psi)
+    }
+
+    /// Advances all systems on one synchronized clock: `dt` is added to
+    /// an accumulator and drained in fixed `SUBSTEP_DT` increments (up to
+    /// `MAX_SUBSTEPS_PER_FRAME` per call), so a variable host frame time
+    /// still produces a stable, reproducible number of physics substeps.
+    ///
+    /// Returns a diagnostics array: `[substep_count, loop_count,
+    /// string_energy_before, string_energy_after, string_drift,
+    /// matrix_energy_before, matrix_energy_after, matrix_drift]`, letting
+    /// the host detect instability and auto-tune damping/substep count.
+    pub fn advance(&mut self, dt: f64) -> Vec<f64> {
+        let string_energy_before = self.strings.get_total_energy();
+        let matrix_energy_before = self.matrices.get_energy();
+
+        // Drop stale backlog (e.g. a backgrounded tab resuming with a huge
+        // `dt`) instead of queueing it — otherwise future `advance` calls
+        // would silently fast-forward through it substep-cap at a time.
+        self.accumulator = (self.accumulator + dt).min(MAX_ACCUMULATOR);
+        let mut substeps = 0u32;
+        while self.accumulator >= SUBSTEP_DT && substeps < MAX_SUBSTEPS_PER_FRAME {
+            self.strings.step(SUBSTEP_DT);
+            self.matrices.step(SUBSTEP_DT);
+            self.accumulator -= SUBSTEP_DT;
+            substeps += 1;
+        }
+
+        let string_energy_after = self.strings.get_total_energy();
+        let matrix_energy_after = self.matrices.get_energy();
+
+        vec![
+            substeps as f64,
+            self.strings.get_loop_count() as f64,
+            string_energy_before,
+            string_energy_after,
+            string_energy_after - string_energy_before,
+            matrix_energy_before,
+            matrix_energy_after,
+            matrix_energy_after - matrix_energy_before,
+        ]
+    }
+
+    pub fn set_string_coupling(&mut self, coupling: f64) {
+        self.strings.set_coupling(coupling);
+    }
+
+    pub fn set_matrix_coupling(&mut self, coupling: f64) {
+        self.matrices.set_coupling(coupling);
+    }
+
+    pub fn set_string_integrator(&mut self, mode: u32) {
+        self.strings.set_integrator(mode);
+    }
+
+    pub fn set_matrix_integrator(&mut self, mode: u32) {
+        self.matrices.set_integrator(mode);
+    }
+
+    pub fn matrix_poke(&mut self, strength: f64) {
+        self.matrices.poke(strength);
+    }
+
+    pub fn matrix_relax(&mut self, iterations: u32) {
+        self.matrices.relax(iterations);
+    }
+
+    /// Returns flat array: [loop_count, n0, color_id0, x0, y0, z0, ..., n1, color_id1, x0, ...]
+    pub fn get_positions(&self) -> Vec<f64> {
+        self.strings.get_positions()
+    }
+
+    /// Returns flat velocity magnitudes per point per loop:
+    /// [loop_count, n0, |v0|, |v1|, ..., n1, |v0|, ...] (no color id)
+    pub fn get_velocities_mag(&self) -> Vec<f64> {
+        self.strings.get_velocities_mag()
+    }
+
+    pub fn get_inertia(&self, loop_index: u32) -> Vec<f64> {
+        self.strings.get_inertia(loop_index)
+    }
+
+    pub fn get_loop_count(&self) -> u32 {
+        self.strings.get_loop_count()
+    }
+
+    pub fn get_spectrum(&self, matrix_index: u32) -> Vec<f64> {
+        self.matrices.get_spectrum(matrix_index)
+    }
+
+    /// Returns connection strengths: flat [a, b, strength, ...] triples
+    pub fn get_connections(&self) -> Vec<f64> {
+        self.matrices.get_connections()
+    }
+}